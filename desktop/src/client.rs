@@ -1,10 +1,20 @@
-use anyhow::Result;
-use reqwest::Client;
-use serde::Deserialize;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Result};
+use rand::Rng;
+use reqwest::{Client, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tokio::net::UdpSocket;
+use tokio::time::timeout;
 
 use crate::config::Config;
 
-#[derive(Debug, Deserialize, Clone)]
+const NTP_PACKET_SIZE: usize = 48;
+const NTP_UNIX_EPOCH_OFFSET_SECS: f64 = 2_208_988_800.0;
+const NTP_QUERY_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct NegotiationPayload {
     pub currency: String,
     pub revenue: f64,
@@ -17,7 +27,7 @@ pub struct NegotiationPayload {
     pub ceiling_discount: f64,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct HealthPayload {
     pub supabase_online: bool,
     pub twilio_online: bool,
@@ -25,6 +35,79 @@ pub struct HealthPayload {
     pub generated_at: String,
 }
 
+/// Local clock drift relative to an NTP server, as classified against
+/// `Config::clock_drift_threshold_secs`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClockStatus {
+    Synced(f64),
+    Degraded(f64),
+    Unknown,
+}
+
+/// Checks the local machine's clock drift against an NTP server using
+/// the SNTP client/server exchange.
+pub struct TimeChecker {
+    server: String,
+    threshold_secs: f64,
+}
+
+impl TimeChecker {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            server: config.ntp_server.clone(),
+            threshold_secs: config.clock_drift_threshold_secs,
+        }
+    }
+
+    /// Queries the NTP server and classifies the resulting offset.
+    /// Runs with a short timeout and treats any failure as `Unknown`
+    /// rather than propagating an error, so the dashboard still renders
+    /// when UDP/NTP is blocked.
+    pub async fn check(&self) -> ClockStatus {
+        match timeout(NTP_QUERY_TIMEOUT, self.query_offset()).await {
+            Ok(Ok(offset)) if offset.abs() > self.threshold_secs => ClockStatus::Degraded(offset),
+            Ok(Ok(offset)) => ClockStatus::Synced(offset),
+            _ => ClockStatus::Unknown,
+        }
+    }
+
+    /// Sends a 48-byte client-mode NTP packet and computes the clock
+    /// offset from the four exchange timestamps: T1 (local send), T2
+    /// (server receive), T3 (server transmit), T4 (local receive).
+    async fn query_offset(&self) -> Result<f64> {
+        let socket = UdpSocket::bind("0.0.0.0:0").await?;
+        socket.connect((self.server.as_str(), 123)).await?;
+
+        let mut packet = [0u8; NTP_PACKET_SIZE];
+        packet[0] = 0x1B; // LI = 0, VN = 3, Mode = 3 (client)
+
+        let t1 = unix_now_secs();
+        socket.send(&packet).await?;
+
+        let mut response = [0u8; NTP_PACKET_SIZE];
+        socket.recv(&mut response).await?;
+        let t4 = unix_now_secs();
+
+        let server_recv = read_ntp_timestamp(&response[32..40]);
+        let server_xmit = read_ntp_timestamp(&response[40..48]);
+
+        Ok(((server_recv - t1) + (server_xmit - t4)) / 2.0)
+    }
+}
+
+fn unix_now_secs() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock before unix epoch")
+        .as_secs_f64()
+}
+
+fn read_ntp_timestamp(bytes: &[u8]) -> f64 {
+    let seconds = u32::from_be_bytes(bytes[0..4].try_into().expect("4-byte slice"));
+    let fraction = u32::from_be_bytes(bytes[4..8].try_into().expect("4-byte slice"));
+    seconds as f64 - NTP_UNIX_EPOCH_OFFSET_SECS + (fraction as f64 / u32::MAX as f64)
+}
+
 pub struct NegotiationClient {
     config: Config,
     http: Client,
@@ -40,17 +123,96 @@ impl NegotiationClient {
 
     pub async fn negotiation(&self) -> Result<NegotiationPayload> {
         let url = format!("{}/negotiation", self.config.base_url);
-        let response = self.http.get(url).send().await?;
-        Ok(response.json::<NegotiationPayload>().await?)
+        self.get_with_retry(&url).await
     }
 
     pub async fn health(&self) -> Result<HealthPayload> {
         let url = format!("{}/health", self.config.base_url);
-        let response = self.http.get(url).send().await?;
-        Ok(response.json::<HealthPayload>().await?)
+        self.get_with_retry(&url).await
+    }
+
+    /// GETs `url` and deserializes the JSON body, retrying transient
+    /// failures (connection errors, timeouts, 5xx, 429) with exponential
+    /// backoff plus jitter, making at most `Config::max_retries` attempts
+    /// in total (the first request included).
+    /// Any other 4xx response is returned immediately without retrying.
+    async fn get_with_retry<T: DeserializeOwned>(&self, url: &str) -> Result<T> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            match self.http.get(url).send().await {
+                Ok(response) => {
+                    let status = response.status();
+                    if status.is_success() {
+                        return Ok(response.json::<T>().await?);
+                    }
+                    if !is_retryable_status(status) || attempt >= self.config.max_retries {
+                        return Err(anyhow!(
+                            "GET {url} failed with status {status} after {attempt} attempt(s)"
+                        ));
+                    }
+                    let retry_after = response
+                        .headers()
+                        .get(reqwest::header::RETRY_AFTER)
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(|value| value.parse::<u64>().ok())
+                        .map(Duration::from_secs);
+                    tokio::time::sleep(retry_after.unwrap_or_else(|| self.backoff_delay(attempt))).await;
+                }
+                Err(err) if is_retryable_error(&err) && attempt < self.config.max_retries => {
+                    tokio::time::sleep(self.backoff_delay(attempt)).await;
+                }
+                Err(err) => {
+                    return Err(anyhow::Error::new(err)
+                        .context(format!("GET {url} failed after {attempt} attempt(s)")));
+                }
+            }
+        }
+    }
+
+    /// Exponential backoff capped at `retry_max_delay_ms`, with ±50% jitter
+    /// so concurrent clients don't retry in lockstep.
+    fn backoff_delay(&self, attempt: u32) -> Duration {
+        let exponential = self
+            .config
+            .retry_base_delay_ms
+            .saturating_mul(1u64 << attempt.min(16));
+        let capped = exponential.min(self.config.retry_max_delay_ms);
+        let jitter = rand::thread_rng().gen_range(0.5..=1.5);
+        Duration::from_millis((capped as f64 * jitter) as u64)
+    }
+
+    /// Sends an SMS alert through the Twilio Messages API using the
+    /// account's Basic auth credentials. Intended for margin breach
+    /// notifications fired from `main`.
+    pub async fn send_alert(&self, body: &str) -> Result<()> {
+        let url = format!(
+            "https://api.twilio.com/2010-04-01/Accounts/{}/Messages.json",
+            self.config.twilio_account_sid
+        );
+        self.http
+            .post(url)
+            .basic_auth(&self.config.twilio_account_sid, Some(&self.config.twilio_auth_token))
+            .form(&[
+                ("From", self.config.twilio_from.as_str()),
+                ("To", self.config.twilio_to.as_str()),
+                ("Body", body),
+            ])
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
     }
 }
 
+fn is_retryable_status(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+fn is_retryable_error(err: &reqwest::Error) -> bool {
+    err.is_connect() || err.is_timeout()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -60,4 +222,23 @@ mod tests {
         let config = Config::default();
         assert!(config.base_url.contains("localhost"));
     }
+
+    #[test]
+    fn retryable_status_covers_5xx_and_429_only() {
+        assert!(is_retryable_status(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+        assert!(!is_retryable_status(StatusCode::BAD_REQUEST));
+    }
+
+    #[test]
+    fn reads_ntp_timestamp_as_unix_seconds() {
+        // 1 Jan 2023 00:00:00 UTC (Unix 1_672_531_200) plus the 2_208_988_800s
+        // between the NTP and Unix epochs, no fraction.
+        let seconds: u32 = 2_208_988_800 + 1_672_531_200;
+        let mut bytes = [0u8; 8];
+        bytes[0..4].copy_from_slice(&seconds.to_be_bytes());
+        assert_eq!(read_ntp_timestamp(&bytes), 1_672_531_200.0);
+    }
 }
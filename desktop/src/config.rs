@@ -1,14 +1,175 @@
 use std::env;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
 
 #[derive(Debug, Clone)]
 pub struct Config {
     pub base_url: String,
+    pub twilio_account_sid: String,
+    pub twilio_auth_token: String,
+    pub twilio_from: String,
+    pub twilio_to: String,
+    /// Maximum number of attempts (including the first) for a single GET.
+    pub max_retries: u32,
+    /// Base delay in milliseconds for exponential backoff between retries.
+    pub retry_base_delay_ms: u64,
+    /// Upper bound in milliseconds on the exponential backoff delay, applied
+    /// before the ±50% jitter (so a jittered delay can reach 1.5x this value).
+    pub retry_max_delay_ms: u64,
+    /// NTP server queried for clock-drift detection.
+    pub ntp_server: String,
+    /// Clock offset, in seconds, beyond which the health panel is marked degraded.
+    pub clock_drift_threshold_secs: f64,
+    /// Append-only JSON-lines file that snapshot history is persisted to.
+    pub history_file: String,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             base_url: env::var("NEGOTIATION_API_BASE").unwrap_or_else(|_| "http://localhost:8000/api/v1".to_string()),
+            twilio_account_sid: env::var("TWILIO_ACCOUNT_SID").unwrap_or_default(),
+            twilio_auth_token: env::var("TWILIO_AUTH_TOKEN").unwrap_or_default(),
+            twilio_from: env::var("TWILIO_FROM").unwrap_or_default(),
+            twilio_to: env::var("TWILIO_TO").unwrap_or_default(),
+            max_retries: 4,
+            retry_base_delay_ms: 200,
+            retry_max_delay_ms: 5_000,
+            ntp_server: env::var("NTP_SERVER").unwrap_or_else(|_| "pool.ntp.org".to_string()),
+            clock_drift_threshold_secs: 2.0,
+            history_file: env::var("NEGOTIATION_HISTORY_FILE")
+                .unwrap_or_else(|_| "negotiation-history.jsonl".to_string()),
+        }
+    }
+}
+
+/// Mirrors `Config` but with every field optional, so a TOML file only
+/// needs to set the values it wants to override.
+#[derive(Debug, Default, Deserialize)]
+struct FileConfig {
+    base_url: Option<String>,
+    twilio_account_sid: Option<String>,
+    twilio_auth_token: Option<String>,
+    twilio_from: Option<String>,
+    twilio_to: Option<String>,
+    max_retries: Option<u32>,
+    retry_base_delay_ms: Option<u64>,
+    retry_max_delay_ms: Option<u64>,
+    ntp_server: Option<String>,
+    clock_drift_threshold_secs: Option<f64>,
+    history_file: Option<String>,
+}
+
+impl Config {
+    /// Whether all four Twilio fields needed to send an SMS alert are set.
+    pub fn twilio_configured(&self) -> bool {
+        !self.twilio_account_sid.is_empty()
+            && !self.twilio_auth_token.is_empty()
+            && !self.twilio_from.is_empty()
+            && !self.twilio_to.is_empty()
+    }
+
+    /// Loads a layered config: built-in defaults, then values from `path`,
+    /// then environment overrides, which take the highest precedence so an
+    /// operator can check a `negotiation.toml` into their deployment while
+    /// still overriding secrets through the environment.
+    pub fn load_from_file(path: &Path) -> Result<Self> {
+        let mut config = Self::default();
+
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("reading config file {}", path.display()))?;
+        let file_config: FileConfig = toml::from_str(&contents)
+            .with_context(|| format!("parsing config file {}", path.display()))?;
+
+        if let Some(base_url) = file_config.base_url {
+            config.base_url = base_url;
+        }
+        if let Some(sid) = file_config.twilio_account_sid {
+            config.twilio_account_sid = sid;
+        }
+        if let Some(token) = file_config.twilio_auth_token {
+            config.twilio_auth_token = token;
+        }
+        if let Some(from) = file_config.twilio_from {
+            config.twilio_from = from;
+        }
+        if let Some(to) = file_config.twilio_to {
+            config.twilio_to = to;
+        }
+        if let Some(max_retries) = file_config.max_retries {
+            config.max_retries = max_retries;
+        }
+        if let Some(base_delay) = file_config.retry_base_delay_ms {
+            config.retry_base_delay_ms = base_delay;
+        }
+        if let Some(max_delay) = file_config.retry_max_delay_ms {
+            config.retry_max_delay_ms = max_delay;
+        }
+        if let Some(ntp_server) = file_config.ntp_server {
+            config.ntp_server = ntp_server;
         }
+        if let Some(threshold) = file_config.clock_drift_threshold_secs {
+            config.clock_drift_threshold_secs = threshold;
+        }
+        if let Some(history_file) = file_config.history_file {
+            config.history_file = history_file;
+        }
+
+        // Re-apply env overrides last so a secret set in the environment
+        // always wins over the same key in the checked-in TOML file.
+        apply_env_overrides(&mut config, |key| env::var(key).ok());
+
+        Ok(config)
+    }
+}
+
+/// Overwrites `config` fields with values from `lookup`, keyed by the same
+/// env var names `Config::default` reads. Takes a lookup function rather
+/// than calling `env::var` directly so tests can exercise the precedence
+/// logic without mutating the process-global environment (which would
+/// race with any other test that reads it, e.g. `Config::default`).
+fn apply_env_overrides(config: &mut Config, lookup: impl Fn(&str) -> Option<String>) {
+    if let Some(base_url) = lookup("NEGOTIATION_API_BASE") {
+        config.base_url = base_url;
+    }
+    if let Some(sid) = lookup("TWILIO_ACCOUNT_SID") {
+        config.twilio_account_sid = sid;
+    }
+    if let Some(token) = lookup("TWILIO_AUTH_TOKEN") {
+        config.twilio_auth_token = token;
+    }
+    if let Some(from) = lookup("TWILIO_FROM") {
+        config.twilio_from = from;
+    }
+    if let Some(to) = lookup("TWILIO_TO") {
+        config.twilio_to = to;
+    }
+    if let Some(ntp_server) = lookup("NTP_SERVER") {
+        config.ntp_server = ntp_server;
+    }
+    if let Some(history_file) = lookup("NEGOTIATION_HISTORY_FILE") {
+        config.history_file = history_file;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_override_beats_file_value() {
+        let mut config = Config {
+            base_url: "http://file-configured:9000/api/v1".to_string(),
+            ..Config::default()
+        };
+
+        apply_env_overrides(&mut config, |key| {
+            (key == "NEGOTIATION_API_BASE").then(|| "http://env-configured:9000/api/v1".to_string())
+        });
+
+        assert_eq!(config.base_url, "http://env-configured:9000/api/v1");
     }
 }
@@ -0,0 +1,265 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::client::{HealthPayload, NegotiationPayload};
+
+/// A single fetched negotiation/health snapshot, timestamped for history.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HistoryEntry {
+    pub generated_at: String,
+    pub envelope: NegotiationPayload,
+    pub health: HealthPayload,
+}
+
+/// Append-only JSON-lines store of fetched snapshots, deduping consecutive
+/// identical ones so unchanged polls don't bloat the file.
+pub struct HistoryStore {
+    path: PathBuf,
+}
+
+impl HistoryStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Appends `entry` unless it's identical (ignoring `generated_at`) to
+    /// the most recently stored entry.
+    pub fn append(&self, entry: &HistoryEntry) -> Result<()> {
+        if let Some(last) = self.recent(1)?.pop() {
+            if snapshot_unchanged(&last, entry) {
+                return Ok(());
+            }
+        }
+
+        let line = serde_json::to_string(entry).context("serializing history entry")?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("opening history file {}", self.path.display()))?;
+        writeln!(file, "{line}").context("writing history entry")?;
+        Ok(())
+    }
+
+    /// Returns up to `limit` of the most recently stored entries, oldest first.
+    pub fn recent(&self, limit: usize) -> Result<Vec<HistoryEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let contents = fs::read_to_string(&self.path)
+            .with_context(|| format!("reading history file {}", self.path.display()))?;
+        let mut entries: Vec<HistoryEntry> = contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).context("parsing history entry"))
+            .collect::<Result<_>>()?;
+        let start = entries.len().saturating_sub(limit);
+        Ok(entries.split_off(start))
+    }
+}
+
+/// True if two entries carry the same revenue/expense/margin/health data,
+/// ignoring each entry's `generated_at` timestamp (including the one
+/// embedded in `HealthPayload::generated_at`, which a live `/health`
+/// endpoint stamps fresh on every poll).
+fn snapshot_unchanged(a: &HistoryEntry, b: &HistoryEntry) -> bool {
+    a.envelope == b.envelope
+        && a.health.supabase_online == b.health.supabase_online
+        && a.health.twilio_online == b.health.twilio_online
+        && a.health.cached_notifications == b.health.cached_notifications
+}
+
+/// Renders `entries` as an RSS 2.0 feed, flagging any snapshot whose
+/// target margin has crossed below its floor margin.
+pub fn render_rss_feed(entries: &[HistoryEntry]) -> String {
+    let mut items = String::new();
+    for entry in entries {
+        let breach = entry.envelope.target_margin < entry.envelope.floor_margin;
+        let title = if breach {
+            format!(
+                "Margin breach: {:.2}% below floor {:.2}%",
+                entry.envelope.target_margin * 100.0,
+                entry.envelope.floor_margin * 100.0
+            )
+        } else {
+            format!(
+                "Snapshot: {:.2}% margin, {:.2} revenue / {:.2} expense",
+                entry.envelope.target_margin * 100.0,
+                entry.envelope.revenue,
+                entry.envelope.expense
+            )
+        };
+
+        let pub_date = rfc822_pub_date(&entry.generated_at)
+            .map(|date| format!("\n      <pubDate>{}</pubDate>", xml_escape(&date)))
+            .unwrap_or_default();
+
+        items.push_str(&format!(
+            "    <item>\n      <title>{}</title>{pub_date}\n      <description>{}</description>\n    </item>\n",
+            xml_escape(&title),
+            xml_escape(&describe(entry)),
+        ));
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rss version=\"2.0\">\n  <channel>\n    <title>Twilio Negotiation History</title>\n    <description>Revenue/expense negotiation snapshots</description>\n{items}  </channel>\n</rss>\n"
+    )
+}
+
+/// Converts an ISO-8601 `generated_at` timestamp to the RFC-822 date
+/// format RSS 2.0's `<pubDate>` requires. Returns `None` if it doesn't
+/// parse, so the caller can omit the element rather than emit a
+/// non-conformant value.
+fn rfc822_pub_date(generated_at: &str) -> Option<String> {
+    DateTime::parse_from_rfc3339(generated_at)
+        .ok()
+        .map(|date| date.to_rfc2822())
+}
+
+fn describe(entry: &HistoryEntry) -> String {
+    format!(
+        "currency={} revenue={:.2} expense={:.2} target_margin={:.2}% floor_margin={:.2}% ceiling_margin={:.2}%",
+        entry.envelope.currency,
+        entry.envelope.revenue,
+        entry.envelope.expense,
+        entry.envelope.target_margin * 100.0,
+        entry.envelope.floor_margin * 100.0,
+        entry.envelope.ceiling_margin * 100.0,
+    )
+}
+
+fn xml_escape(input: &str) -> String {
+    input.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Writes `feed` to `path` if given, otherwise to stdout.
+pub fn write_feed(feed: &str, path: Option<&Path>) -> Result<()> {
+    match path {
+        Some(path) => {
+            fs::write(path, feed).with_context(|| format!("writing feed to {}", path.display()))
+        }
+        None => {
+            print!("{feed}");
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_payload(target_margin: f64) -> NegotiationPayload {
+        NegotiationPayload {
+            currency: "USD".to_string(),
+            revenue: 100.0,
+            expense: 40.0,
+            target_margin,
+            floor_margin: 0.2,
+            ceiling_margin: 0.5,
+            target_discount: 0.1,
+            floor_discount: 0.0,
+            ceiling_discount: 0.3,
+        }
+    }
+
+    fn sample_health() -> HealthPayload {
+        HealthPayload {
+            supabase_online: true,
+            twilio_online: true,
+            cached_notifications: 0,
+            generated_at: "2026-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    fn temp_store(label: &str) -> (PathBuf, HistoryStore) {
+        let path = std::env::temp_dir().join(format!("history-test-{}-{label}", std::process::id()));
+        fs::remove_file(&path).ok();
+        let store = HistoryStore::new(&path);
+        (path, store)
+    }
+
+    #[test]
+    fn dedupes_consecutive_identical_snapshots() {
+        let (path, store) = temp_store("identical");
+
+        let entry = HistoryEntry {
+            generated_at: "2026-01-01T00:00:00Z".to_string(),
+            envelope: sample_payload(0.3),
+            health: sample_health(),
+        };
+        store.append(&entry).unwrap();
+        store.append(&entry).unwrap();
+
+        let changed = HistoryEntry {
+            generated_at: "2026-01-01T00:05:00Z".to_string(),
+            envelope: sample_payload(0.1),
+            health: sample_health(),
+        };
+        store.append(&changed).unwrap();
+
+        assert_eq!(store.recent(10).unwrap().len(), 2);
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn dedupes_unchanged_snapshot_with_fresh_timestamp() {
+        let (path, store) = temp_store("fresh-timestamp");
+
+        let first = HistoryEntry {
+            generated_at: "2026-01-01T00:00:00Z".to_string(),
+            envelope: sample_payload(0.3),
+            health: sample_health(),
+        };
+        store.append(&first).unwrap();
+
+        // Same revenue/expense/margin/health data, but a `/health` poll
+        // stamps a brand new `generated_at` every time.
+        let mut second = first.clone();
+        second.generated_at = "2026-01-01T00:05:00Z".to_string();
+        second.health.generated_at = "2026-01-01T00:05:00Z".to_string();
+        store.append(&second).unwrap();
+
+        assert_eq!(store.recent(10).unwrap().len(), 1);
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn flags_margin_breach_in_feed() {
+        let entries = vec![HistoryEntry {
+            generated_at: "2026-01-01T00:00:00Z".to_string(),
+            envelope: sample_payload(0.1),
+            health: sample_health(),
+        }];
+        let feed = render_rss_feed(&entries);
+        assert!(feed.contains("Margin breach"));
+    }
+
+    #[test]
+    fn pub_date_is_rfc822_not_iso8601() {
+        let entries = vec![HistoryEntry {
+            generated_at: "2026-01-01T00:00:00Z".to_string(),
+            envelope: sample_payload(0.3),
+            health: sample_health(),
+        }];
+        let feed = render_rss_feed(&entries);
+        assert!(feed.contains("<pubDate>Thu, 1 Jan 2026 00:00:00 +0000</pubDate>"));
+        assert!(!feed.contains("2026-01-01T00:00:00Z"));
+    }
+
+    #[test]
+    fn unparseable_timestamp_omits_pub_date() {
+        let entries = vec![HistoryEntry {
+            generated_at: "not-a-timestamp".to_string(),
+            envelope: sample_payload(0.3),
+            health: sample_health(),
+        }];
+        let feed = render_rss_feed(&entries);
+        assert!(!feed.contains("<pubDate>"));
+    }
+}
@@ -1,33 +1,153 @@
 mod client;
 mod config;
+mod history;
 
-use anyhow::Result;
-use clap::Parser;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use clap::{Parser, Subcommand};
 use colored::*;
+use cron::Schedule;
 use tokio::runtime::Runtime;
+use tokio::time::sleep;
 
-use client::NegotiationClient;
+use client::{ClockStatus, NegotiationClient, TimeChecker};
 use config::Config;
+use history::{HistoryEntry, HistoryStore};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about = "Twilio negotiation desktop console", long_about = None)]
 struct Args {
     #[arg(short, long, default_value = "true")]
     pretty: bool,
+
+    /// Path to a TOML config file layered on top of the built-in defaults;
+    /// environment variables still override its values.
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Fetch and render the dashboard a single time. The default.
+    Once,
+    /// Poll on a cron schedule, re-rendering the dashboard in place.
+    Watch {
+        /// Cron expression, e.g. "0 */5 * * * *" (sec min hour dom mon dow).
+        #[arg(long)]
+        schedule: String,
+    },
+    /// Emit recent snapshot history as an RSS 2.0 feed. This is the
+    /// `--feed` output mode, shaped as a subcommand to match the
+    /// `once`/`watch` structure rather than a flag on those subcommands.
+    Feed {
+        /// File to write the feed to; defaults to stdout.
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// Maximum number of history entries to include.
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+    },
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
     let runtime = Runtime::new()?;
-    let config = Config::default();
+    let config = match &args.config {
+        Some(path) => Config::load_from_file(path)?,
+        None => Config::default(),
+    };
     let client = NegotiationClient::new(config.clone());
+    let time_checker = TimeChecker::new(&config);
+    let history = HistoryStore::new(config.history_file.clone());
+
+    match args.command.unwrap_or(Command::Once) {
+        Command::Once => {
+            runtime.block_on(fetch_and_render(&client, &time_checker, &history, &config, args.pretty))
+        }
+        Command::Watch { schedule } => {
+            let schedule: Schedule = schedule.parse().context("parsing cron schedule")?;
+            runtime.block_on(watch(&client, &time_checker, &history, &config, &schedule, args.pretty))
+        }
+        Command::Feed { output, limit } => {
+            let entries = history.recent(limit)?;
+            let feed = history::render_rss_feed(&entries);
+            history::write_feed(&feed, output.as_deref())
+        }
+    }
+}
+
+/// Sleeps until each upcoming fire time in `schedule`, then re-fetches and
+/// re-renders the dashboard. Runs until interrupted.
+async fn watch(
+    client: &NegotiationClient,
+    time_checker: &TimeChecker,
+    history: &HistoryStore,
+    config: &Config,
+    schedule: &Schedule,
+    pretty: bool,
+) -> Result<()> {
+    for fire_time in schedule.upcoming(Utc) {
+        let now = Utc::now();
+        if let Ok(delay) = (fire_time - now).to_std() {
+            sleep(delay).await;
+        }
+        clear_screen();
+        fetch_and_render(client, time_checker, history, config, pretty).await?;
+    }
+    Ok(())
+}
+
+/// Clears the terminal and moves the cursor home so each poll in `watch`
+/// redraws the dashboard in place instead of scrolling a fresh copy.
+fn clear_screen() {
+    print!("\x1b[2J\x1b[H");
+    let _ = io::stdout().flush();
+}
 
-    let envelope = runtime.block_on(client.negotiation());
-    let health = runtime.block_on(client.health());
+async fn fetch_and_render(
+    client: &NegotiationClient,
+    time_checker: &TimeChecker,
+    history: &HistoryStore,
+    config: &Config,
+    pretty: bool,
+) -> Result<()> {
+    let envelope = client.negotiation().await;
+    let health = client.health().await;
 
     match (envelope, health) {
         (Ok(envelope), Ok(health)) => {
-            render_dashboard(&config, &envelope, &health, args.pretty);
+            let clock = time_checker.check().await;
+            render_dashboard(config, &envelope, &health, clock, pretty);
+
+            let entry = HistoryEntry {
+                generated_at: health.generated_at.clone(),
+                envelope: envelope.clone(),
+                health: health.clone(),
+            };
+            if let Err(err) = history.append(&entry) {
+                eprintln!("{}", format!("Failed to persist history entry: {err}").red());
+            }
+
+            if let Some(reason) = breach_reason(&envelope) {
+                if config.twilio_configured() {
+                    let body = format!("Negotiation margin alert: {reason}");
+                    if let Err(err) = client.send_alert(&body).await {
+                        eprintln!("{}", format!("Failed to send Twilio alert: {err}").red());
+                    }
+                } else {
+                    eprintln!(
+                        "{}",
+                        format!("Margin breach ({reason}) but Twilio is not configured; skipping SMS alert")
+                            .yellow()
+                    );
+                }
+            }
         }
         (negotiation, health) => {
             eprintln!("{}", "Failed to fetch negotiation data".red().bold());
@@ -47,6 +167,7 @@ fn render_dashboard(
     config: &Config,
     envelope: &client::NegotiationPayload,
     health: &client::HealthPayload,
+    clock: ClockStatus,
     pretty: bool,
 ) {
     println!("{}", "Twilio Negotiation Snapshot".bright_cyan().bold());
@@ -74,13 +195,44 @@ fn render_dashboard(
             status_badge(health.twilio_online),
             health.cached_notifications
         );
-        println!("Last Sync: {}", health.generated_at);
+        println!("Last Sync: {}{}", health.generated_at, clock_badge(clock));
     } else {
         println!(
             "Health: supabase_online={} twilio_online={} cached_notifications={}",
             health.supabase_online, health.twilio_online, health.cached_notifications
         );
-        println!("last_sync={}", health.generated_at);
+        println!("last_sync={} clock={:?}", health.generated_at, clock);
+    }
+}
+
+/// Renders a ` [DEGRADED] (offset N.NNs)` suffix when the local clock has drifted beyond
+/// the configured threshold; otherwise an empty string.
+fn clock_badge(clock: ClockStatus) -> String {
+    match clock {
+        ClockStatus::Degraded(offset) => {
+            format!(" {} (offset {:.2}s)", "[DEGRADED]".yellow().bold(), offset)
+        }
+        ClockStatus::Synced(_) | ClockStatus::Unknown => String::new(),
+    }
+}
+
+/// Returns a human-readable reason when the envelope has breached its
+/// margin floor or discount ceiling, or `None` if it's within bounds.
+fn breach_reason(envelope: &client::NegotiationPayload) -> Option<String> {
+    if envelope.target_margin < envelope.floor_margin {
+        Some(format!(
+            "target margin {:.2}% fell below floor {:.2}%",
+            envelope.target_margin * 100.0,
+            envelope.floor_margin * 100.0
+        ))
+    } else if envelope.target_discount > envelope.ceiling_discount {
+        Some(format!(
+            "target discount {:.2}% exceeded ceiling {:.2}%",
+            envelope.target_discount * 100.0,
+            envelope.ceiling_discount * 100.0
+        ))
+    } else {
+        None
     }
 }
 